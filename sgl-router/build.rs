@@ -1,9 +1,25 @@
 use std::process::Command;
 
+#[path = "src/build_support.rs"]
+mod build_support;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Only regenerate if the proto file changes
+    // Only regenerate if the proto files, lockfile, pyproject.toml, the
+    // release pipeline's build_meta.toml fallback, or the current git ref
+    // change. `.git/HEAD` only moves on checkout/branch-switch and
+    // `packed-refs` only on `git pack-refs`/gc, so a normal commit on the
+    // current branch is covered separately, via its loose ref file.
     println!("cargo:rerun-if-changed=src/proto/sglang_scheduler.proto");
+    println!("cargo:rerun-if-changed=src/proto/build_info.proto");
     println!("cargo:rerun-if-changed=pyproject.toml");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-changed=../Cargo.lock");
+    println!("cargo:rerun-if-changed=src/build_meta.toml");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/packed-refs");
+    for path in git_ref_watch_paths() {
+        println!("cargo:rerun-if-changed={}", path);
+    }
 
     // Configure tonic-prost-build for gRPC code generation
     tonic_prost_build::configure()
@@ -12,8 +28,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build_client(true)
         // Allow proto3 optional fields
         .protoc_arg("--experimental_allow_proto3_optional")
-        // Compile the proto file
-        .compile_protos(&["src/proto/sglang_scheduler.proto"], &["src/proto"])?;
+        // Compile the scheduler proto and the build-info version service
+        .compile_protos(
+            &["src/proto/sglang_scheduler.proto", "src/proto/build_info.proto"],
+            &["src/proto"],
+        )?;
 
     println!("cargo:warning=Protobuf compilation completed successfully");
 
@@ -29,18 +48,104 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .to_string();
     println!("cargo:rustc-env=SG_ROUTER_BUILD_TIME={}", build_time);
 
-    // Try to get Git branch
-    let git_branch = get_git_branch().unwrap_or_else(|| "unknown".to_string());
+    // Load the fallback provenance baked in by the release pipeline, for
+    // builds (sdists, wheels, vendored path/git deps) that ship without a
+    // `.git` directory at all.
+    let build_meta = read_build_meta();
+
+    // Every git-derived field below resolves in the same order: a live
+    // `git` call first, then (only if git itself is unavailable) an
+    // `SG_ROUTER_GIT_*` env override from the packaging step, then the
+    // committed `build_meta.toml` fallback, then "unknown". This keeps
+    // provenance populated for git-less builds instead of silently
+    // degrading, without letting a stray leftover env var clobber a
+    // perfectly good live git checkout.
+    let git_branch = resolve_git_field(
+        "SG_ROUTER_GIT_BRANCH",
+        get_git_branch(),
+        &build_meta,
+        "branch",
+    );
     println!("cargo:rustc-env=SG_ROUTER_GIT_BRANCH={}", git_branch);
 
-    // Try to get Git commit hash
-    let git_commit = get_git_commit().unwrap_or_else(|| "unknown".to_string());
+    let git_commit = resolve_git_field(
+        "SG_ROUTER_GIT_COMMIT",
+        get_git_commit(),
+        &build_meta,
+        "commit",
+    );
     println!("cargo:rustc-env=SG_ROUTER_GIT_COMMIT={}", git_commit);
 
-    // Try to get Git status (clean/dirty)
-    let git_status = get_git_status().unwrap_or_else(|| "unknown".to_string());
+    let git_status = resolve_git_field(
+        "SG_ROUTER_GIT_STATUS",
+        get_git_status(),
+        &build_meta,
+        "status",
+    );
     println!("cargo:rustc-env=SG_ROUTER_GIT_STATUS={}", git_status);
 
+    let git_tag = resolve_git_field("SG_ROUTER_GIT_TAG", get_git_tag(), &build_meta, "tag");
+    println!("cargo:rustc-env=SG_ROUTER_GIT_TAG={}", git_tag);
+
+    let git_commit_date_rfc2822 = resolve_git_field(
+        "SG_ROUTER_GIT_COMMIT_DATE_2822",
+        get_git_commit_field("%cD"),
+        &build_meta,
+        "commit_date_2822",
+    );
+    println!(
+        "cargo:rustc-env=SG_ROUTER_GIT_COMMIT_DATE_2822={}",
+        git_commit_date_rfc2822
+    );
+    let git_commit_date_rfc3339 = resolve_git_field(
+        "SG_ROUTER_GIT_COMMIT_DATE_3339",
+        get_git_commit_field("%cI"),
+        &build_meta,
+        "commit_date_3339",
+    );
+    println!(
+        "cargo:rustc-env=SG_ROUTER_GIT_COMMIT_DATE_3339={}",
+        git_commit_date_rfc3339
+    );
+
+    let git_commit_author_name = resolve_git_field(
+        "SG_ROUTER_GIT_COMMIT_AUTHOR_NAME",
+        get_git_commit_field("%an"),
+        &build_meta,
+        "commit_author_name",
+    );
+    println!(
+        "cargo:rustc-env=SG_ROUTER_GIT_COMMIT_AUTHOR_NAME={}",
+        git_commit_author_name
+    );
+    let git_commit_author_email = resolve_git_field(
+        "SG_ROUTER_GIT_COMMIT_AUTHOR_EMAIL",
+        get_git_commit_field("%ae"),
+        &build_meta,
+        "commit_author_email",
+    );
+    println!(
+        "cargo:rustc-env=SG_ROUTER_GIT_COMMIT_AUTHOR_EMAIL={}",
+        git_commit_author_email
+    );
+
+    // Try to get the list of modified files (porcelain status), so a
+    // dirty build can say exactly what's uncommitted rather than just
+    // "dirty". There's no sensible build_meta.toml fallback for this one
+    // (a release artifact has nothing to be dirty relative to), so it
+    // just goes through the env override.
+    let git_dirty_files = get_git_dirty_files().unwrap_or_else(|| {
+        std::env::var("SG_ROUTER_GIT_DIRTY_FILES")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(|v| v.split(',').map(|s| s.to_string()).collect())
+            .unwrap_or_default()
+    });
+    println!(
+        "cargo:rustc-env=SG_ROUTER_GIT_DIRTY_FILES={}",
+        git_dirty_files.join(",")
+    );
+
     // Get Rustc version
     let rustc_version = get_rustc_version().unwrap_or_else(|| "unknown".to_string());
     println!("cargo:rustc-env=SG_ROUTER_RUSTC_VERSION={}", rustc_version);
@@ -64,9 +169,115 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
     println!("cargo:rustc-env=SG_ROUTER_BUILD_MODE={}", build_mode);
 
+    // Detect the Rust toolchain release channel (stable/beta/nightly)
+    let rust_channel = get_rust_channel().unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SG_ROUTER_RUST_CHANNEL={}", rust_channel);
+
+    // Detect which CI system (if any) produced this build
+    let ci_system = get_ci_system();
+    println!("cargo:rustc-env=SG_ROUTER_CI={}", ci_system);
+
+    // Compose the canonical version string, reconciling the pyproject
+    // version with the git tag/dirty state.
+    let full_version = compute_full_version(&version, &git_commit, &git_status, &build_meta);
+    println!("cargo:rustc-env=SG_ROUTER_FULL_VERSION={}", full_version);
+
+    // Write the full build manifest (features, dependencies, target, ...)
+    // into OUT_DIR so it can be `include!`d and served alongside the
+    // version info, similar in spirit to the `built` crate.
+    write_build_manifest(&build_mode, &target_triple)?;
+
+    Ok(())
+}
+
+/// Generate `build_info.rs` (for `include!`) and `build_info.json` (for
+/// operators who want to inspect a binary's provenance without running
+/// it) into `OUT_DIR`.
+fn write_build_manifest(
+    build_mode: &str,
+    target_triple: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::env::var("OUT_DIR")?;
+
+    let features = enabled_features();
+    let dependencies = dependencies_from_lockfile("../Cargo.lock")
+        .or_else(|| dependencies_from_lockfile("Cargo.lock"))
+        .unwrap_or_default();
+    let host_triple = std::env::var("HOST").unwrap_or_else(|_| "unknown".to_string());
+    let compile_timestamp = chrono::Utc::now().to_rfc3339();
+
+    let features_rs = features
+        .iter()
+        .map(|f| format!("    {:?},", f))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let deps_rs = dependencies
+        .iter()
+        .map(|(name, version)| format!("    ({:?}, {:?}),", name, version))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let build_info_rs = format!(
+        "/// Cargo features that were enabled for this build.\n\
+         pub const BUILD_FEATURES: &[&str] = &[\n{features}\n];\n\n\
+         /// Resolved `(name, version)` pairs from `Cargo.lock` at build time.\n\
+         pub const BUILD_DEPENDENCIES: &[(&str, &str)] = &[\n{deps}\n];\n\n\
+         /// Optimization profile (`debug` or `release`).\n\
+         pub const BUILD_PROFILE: &str = {profile:?};\n\n\
+         /// Triple of the machine that compiled this binary.\n\
+         pub const BUILD_HOST_TRIPLE: &str = {host:?};\n\n\
+         /// Triple this binary was compiled for.\n\
+         pub const BUILD_TARGET_TRIPLE: &str = {target:?};\n\n\
+         /// RFC 3339 timestamp of when this binary was compiled.\n\
+         pub const BUILD_COMPILE_TIMESTAMP: &str = {ts:?};\n",
+        features = features_rs,
+        deps = deps_rs,
+        profile = build_mode,
+        host = host_triple,
+        target = target_triple,
+        ts = compile_timestamp,
+    );
+    std::fs::write(format!("{out_dir}/build_info.rs"), build_info_rs)?;
+
+    // Build a real JSON value rather than hand-formatting strings:
+    // Rust's `{:?}` Debug-escapes control/non-printable characters as
+    // variable-width `\u{xxxx}`, which isn't valid JSON (`\uXXXX`, fixed
+    // 4-hex, no braces) and would produce a malformed manifest for any
+    // dependency/feature name or target triple containing one.
+    let build_info_json = serde_json::json!({
+        "features": features,
+        "dependencies": dependencies
+            .iter()
+            .map(|(name, version)| serde_json::json!({"name": name, "version": version}))
+            .collect::<Vec<_>>(),
+        "profile": build_mode,
+        "host_triple": host_triple,
+        "target_triple": target_triple,
+        "compile_timestamp": compile_timestamp,
+    });
+    std::fs::write(
+        format!("{out_dir}/build_info.json"),
+        serde_json::to_string(&build_info_json)?,
+    )?;
+
     Ok(())
 }
 
+/// Collect the Cargo features enabled for this build from the
+/// `CARGO_FEATURE_*` env vars Cargo sets during compilation. See
+/// [`build_support::reconstruct_features`] for the (lossy) reconstruction.
+fn enabled_features() -> Vec<String> {
+    let keys: Vec<String> = std::env::vars().map(|(key, _)| key).collect();
+    build_support::reconstruct_features(keys.iter().map(String::as_str))
+}
+
+/// Parse `name = "..."` / `version = "..."` pairs out of a `Cargo.lock`
+/// file without pulling in a TOML parser as a build dependency.
+fn dependencies_from_lockfile(path: &str) -> Option<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(build_support::parse_lockfile_dependencies(&content))
+}
+
 fn read_version_from_pyproject(field: &str) -> Result<String, Box<dyn std::error::Error>> {
     let content = std::fs::read_to_string("pyproject.toml")?;
 
@@ -88,6 +299,37 @@ fn read_version_from_pyproject(field: &str) -> Result<String, Box<dyn std::error
     Err(format!("{} not found in pyproject.toml", field).into())
 }
 
+/// Paths Cargo should watch to catch the current branch moving to a new
+/// commit: `HEAD`'s resolved ref file (e.g. `.git/refs/heads/main`) when
+/// on a branch, falling back to watching the whole `refs/heads`
+/// directory if that can't be resolved (git missing, detached HEAD,
+/// non-standard layout, ...) so a branch switch or rename is still
+/// caught.
+fn git_ref_watch_paths() -> Vec<String> {
+    let symbolic_ref = Command::new("git")
+        .args(&["symbolic-ref", "-q", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string());
+
+    if let Some(ref_name) = symbolic_ref {
+        let git_path = Command::new("git")
+            .args(&["rev-parse", "--git-path", &ref_name])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| s.trim().to_string());
+        if let Some(path) = git_path {
+            return vec![path];
+        }
+    }
+
+    vec![".git/refs/heads".to_string()]
+}
+
 fn get_git_branch() -> Option<String> {
     let output = Command::new("git")
         .args(&["rev-parse", "--abbrev-ref", "HEAD"])
@@ -136,6 +378,135 @@ fn get_git_status() -> Option<String> {
     }
 }
 
+/// Resolve a single git-derived field, preferring (in order) a live
+/// `git` result, and only falling back to an `SG_ROUTER_GIT_*` override
+/// from the packaging step or the committed `build_meta.toml` when git
+/// itself is unavailable (no `.git` directory, git not installed, ...).
+/// Finally falls back to `"unknown"`.
+fn resolve_git_field(
+    env_override: &str,
+    live: Option<String>,
+    build_meta: &std::collections::HashMap<String, String>,
+    meta_key: &str,
+) -> String {
+    build_support::resolve_git_field(std::env::var(env_override).ok(), live, build_meta, meta_key)
+}
+
+/// Read the release pipeline's fallback provenance file, if present.
+///
+/// `src/build_meta.toml` is generated and committed by the release
+/// pipeline for packages that ship without a `.git` directory (sdists,
+/// wheels, vendored `path`/`git` dependencies), as a simple flat
+/// `key = "value"` file. Missing or unparsable files just mean no
+/// fallback is available.
+fn read_build_meta() -> std::collections::HashMap<String, String> {
+    std::fs::read_to_string("src/build_meta.toml")
+        .map(|content| build_support::parse_build_meta(&content))
+        .unwrap_or_default()
+}
+
+/// Parse the Rust release channel (`stable`, `beta`, or `nightly`) out of
+/// `rustc -vV`'s `release:` line, e.g. `1.80.0-nightly` -> `nightly`.
+fn get_rust_channel() -> Option<String> {
+    let output = Command::new("rustc").args(&["-vV"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    build_support::parse_rust_channel(&String::from_utf8(output.stdout).ok()?)
+}
+
+/// Identify the CI system this build ran on, if any, by checking the
+/// well-known environment variables each one sets. Defaults to `"local"`
+/// for a developer machine.
+fn get_ci_system() -> String {
+    build_support::detect_ci_system(|key| std::env::var_os(key).is_some())
+}
+
+/// Compose a single canonical version string from the pyproject baseline
+/// and the current git state:
+///
+/// 1. clean tree, `HEAD` exactly tagged `v<version>` -> `<version>`
+/// 2. dirty tree -> `<version>+<shorthash>-modified`
+/// 3. clean tree, no matching tag (e.g. a `-dev` pre-release) -> `<version>+<shorthash>`
+/// 4. no commit known (from git, an env override, or `build_meta.toml`) -> `<version>`
+///
+/// Never fails the build; falls back to the bare pyproject version when
+/// no commit is known from any source.
+fn compute_full_version(
+    version: &str,
+    git_commit: &str,
+    git_status: &str,
+    build_meta: &std::collections::HashMap<String, String>,
+) -> String {
+    let is_dirty = git_status == "dirty";
+    let exact_tag = get_exact_git_tag().or_else(|| build_meta.get("tag").cloned());
+    build_support::compute_full_version(version, git_commit, is_dirty, exact_tag.as_deref())
+}
+
+/// The tag exactly pointing at `HEAD`, if any (`git describe --tags
+/// --exact-match`). Returns `None` when `HEAD` isn't tagged or git isn't
+/// available.
+fn get_exact_git_tag() -> Option<String> {
+    let output = Command::new("git")
+        .args(&["describe", "--tags", "--exact-match"])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .ok()
+            .map(|s| s.trim().to_string())
+    } else {
+        None
+    }
+}
+
+fn get_git_tag() -> Option<String> {
+    let output = Command::new("git")
+        .args(&["describe", "--tags"])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .ok()
+            .map(|s| s.trim().to_string())
+    } else {
+        None
+    }
+}
+
+fn get_git_commit_field(format: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(&["log", "-1", &format!("--format={}", format)])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    } else {
+        None
+    }
+}
+
+fn get_git_dirty_files() -> Option<Vec<String>> {
+    let output = Command::new("git")
+        .args(&["status", "--porcelain"])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(build_support::parse_dirty_files(
+            &String::from_utf8(output.stdout).ok()?,
+        ))
+    } else {
+        None
+    }
+}
+
 fn get_rustc_version() -> Option<String> {
     let output = Command::new("rustc").arg("--version").output().ok()?;
 
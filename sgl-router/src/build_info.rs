@@ -0,0 +1,200 @@
+//! Structured build/version metadata for the router binary.
+//!
+//! The values here are populated once, at compile time, from the
+//! `SG_ROUTER_*` environment variables that `build.rs` exports via
+//! `cargo:rustc-env`. Call [`BuildInfo::current`] to get a `'static`
+//! snapshot rather than reaching for `env!` one field at a time.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Generated by build.rs: BUILD_FEATURES, BUILD_DEPENDENCIES, BUILD_PROFILE,
+// BUILD_HOST_TRIPLE, BUILD_TARGET_TRIPLE, BUILD_COMPILE_TIMESTAMP.
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
+/// Everything we know about how this binary was built.
+///
+/// Mirrors the shape of `rustc_tools_util::VersionInfo`: a single struct
+/// that can be printed for `--version` output (see its `Display` impl,
+/// used by `main.rs`) or serialized as JSON for the `/version` HTTP
+/// handler in this module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub project_name: String,
+    pub version: String,
+    /// Canonical version string with tag/dirty reconciliation applied,
+    /// e.g. `1.2.3`, `1.2.3+abc1234`, or `1.2.3+abc1234-modified`.
+    pub full_version: String,
+    pub git_branch: String,
+    pub git_commit: String,
+    pub git_status: String,
+    /// Nearest `git describe --tags` to the built commit.
+    pub git_tag: String,
+    /// Commit date, RFC 2822 form.
+    pub git_commit_date_rfc2822: String,
+    /// Commit date, RFC 3339 form.
+    pub git_commit_date_rfc3339: String,
+    /// Commit author's name.
+    pub git_commit_author_name: String,
+    /// Commit author's email.
+    pub git_commit_author_email: String,
+    /// `git status --porcelain` lines for a dirty tree, one per modified file.
+    pub git_dirty_files: Vec<String>,
+    pub build_time: String,
+    pub rustc_version: String,
+    pub cargo_version: String,
+    pub target_triple: String,
+    pub build_mode: String,
+    /// Rust toolchain release channel (`stable`, `beta`, or `nightly`).
+    pub rust_channel: String,
+    /// CI system this build ran on (`github-actions`, `gitlab-ci`, ...),
+    /// or `local` for a developer machine.
+    pub ci: String,
+
+    /// Cargo features enabled for this build.
+    pub features: Vec<String>,
+    /// Resolved `(name, version)` dependency pairs from `Cargo.lock`.
+    pub dependencies: Vec<(String, String)>,
+    /// Host triple of the machine that produced this binary.
+    pub host_triple: String,
+    /// RFC 3339 compile timestamp.
+    pub compile_timestamp: String,
+}
+
+impl BuildInfo {
+    /// Build a [`BuildInfo`] from the env vars baked in by `build.rs`.
+    pub fn current() -> Self {
+        BuildInfo {
+            project_name: env!("SG_ROUTER_PROJECT_NAME").to_string(),
+            version: env!("SG_ROUTER_VERSION").to_string(),
+            full_version: env!("SG_ROUTER_FULL_VERSION").to_string(),
+            git_branch: env!("SG_ROUTER_GIT_BRANCH").to_string(),
+            git_commit: env!("SG_ROUTER_GIT_COMMIT").to_string(),
+            git_status: env!("SG_ROUTER_GIT_STATUS").to_string(),
+            git_tag: env!("SG_ROUTER_GIT_TAG").to_string(),
+            git_commit_date_rfc2822: env!("SG_ROUTER_GIT_COMMIT_DATE_2822").to_string(),
+            git_commit_date_rfc3339: env!("SG_ROUTER_GIT_COMMIT_DATE_3339").to_string(),
+            git_commit_author_name: env!("SG_ROUTER_GIT_COMMIT_AUTHOR_NAME").to_string(),
+            git_commit_author_email: env!("SG_ROUTER_GIT_COMMIT_AUTHOR_EMAIL").to_string(),
+            git_dirty_files: env!("SG_ROUTER_GIT_DIRTY_FILES")
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect(),
+            build_time: env!("SG_ROUTER_BUILD_TIME").to_string(),
+            rustc_version: env!("SG_ROUTER_RUSTC_VERSION").to_string(),
+            cargo_version: env!("SG_ROUTER_CARGO_VERSION").to_string(),
+            target_triple: env!("SG_ROUTER_TARGET_TRIPLE").to_string(),
+            build_mode: env!("SG_ROUTER_BUILD_MODE").to_string(),
+            rust_channel: env!("SG_ROUTER_RUST_CHANNEL").to_string(),
+            ci: env!("SG_ROUTER_CI").to_string(),
+
+            features: BUILD_FEATURES.iter().map(|f| f.to_string()).collect(),
+            dependencies: BUILD_DEPENDENCIES
+                .iter()
+                .map(|(name, version)| (name.to_string(), version.to_string()))
+                .collect(),
+            host_triple: BUILD_HOST_TRIPLE.to_string(),
+            compile_timestamp: BUILD_COMPILE_TIMESTAMP.to_string(),
+        }
+    }
+}
+
+impl BuildInfo {
+    /// Serialize this [`BuildInfo`] the same way [`version_handler`] does.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("BuildInfo always serializes")
+    }
+}
+
+/// `GET /version` handler: returns the current [`BuildInfo`] as JSON.
+///
+/// Mount with `.route("/version", get(version_handler))` wherever the
+/// rest of the router's HTTP routes are registered. See [`proto`] for
+/// the gRPC equivalent.
+pub async fn version_handler() -> axum::Json<BuildInfo> {
+    axum::Json(BuildInfo::current())
+}
+
+/// Generated gRPC types/traits for `src/proto/build_info.proto`, plus the
+/// `BuildInfoService` implementation that backs the `GetVersion` RPC —
+/// the gRPC counterpart to [`version_handler`].
+pub mod proto {
+    tonic::include_proto!("sglang.build_info");
+
+    use super::BuildInfo;
+    use build_info_service_server::BuildInfoService;
+
+    impl From<&BuildInfo> for BuildInfoResponse {
+        fn from(info: &BuildInfo) -> Self {
+            BuildInfoResponse {
+                project_name: info.project_name.clone(),
+                version: info.version.clone(),
+                full_version: info.full_version.clone(),
+                git_branch: info.git_branch.clone(),
+                git_commit: info.git_commit.clone(),
+                git_status: info.git_status.clone(),
+                git_tag: info.git_tag.clone(),
+                git_commit_date_rfc2822: info.git_commit_date_rfc2822.clone(),
+                git_commit_date_rfc3339: info.git_commit_date_rfc3339.clone(),
+                git_commit_author_name: info.git_commit_author_name.clone(),
+                git_commit_author_email: info.git_commit_author_email.clone(),
+                git_dirty_files: info.git_dirty_files.clone(),
+                build_time: info.build_time.clone(),
+                rustc_version: info.rustc_version.clone(),
+                cargo_version: info.cargo_version.clone(),
+                target_triple: info.target_triple.clone(),
+                build_mode: info.build_mode.clone(),
+                rust_channel: info.rust_channel.clone(),
+                ci: info.ci.clone(),
+                features: info.features.clone(),
+                dependencies: info
+                    .dependencies
+                    .iter()
+                    .map(|(name, version)| Dependency {
+                        name: name.clone(),
+                        version: version.clone(),
+                    })
+                    .collect(),
+                host_triple: info.host_triple.clone(),
+                compile_timestamp: info.compile_timestamp.clone(),
+            }
+        }
+    }
+
+    /// gRPC service backing the `BuildInfoService/GetVersion` RPC.
+    ///
+    /// Register with
+    /// `.add_service(build_info_service_server::BuildInfoServiceServer::new(BuildInfoGrpc))`
+    /// on the router's `tonic::transport::Server` builder, alongside the
+    /// scheduler service.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct BuildInfoGrpc;
+
+    #[tonic::async_trait]
+    impl BuildInfoService for BuildInfoGrpc {
+        async fn get_version(
+            &self,
+            _request: tonic::Request<GetVersionRequest>,
+        ) -> Result<tonic::Response<BuildInfoResponse>, tonic::Status> {
+            Ok(tonic::Response::new((&BuildInfo::current()).into()))
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    /// Renders as `sglang-router 1.2.3 (abc1234 2024-05-01, dirty)`,
+    /// matching the one-line banner style of `rustc_tools_util::VersionInfo`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = self
+            .git_commit_date_rfc3339
+            .split('T')
+            .next()
+            .unwrap_or("unknown");
+        write!(
+            f,
+            "{} {} ({} {}, {})",
+            self.project_name, self.version, self.git_commit, date, self.git_status
+        )
+    }
+}
@@ -0,0 +1,310 @@
+//! Pure build-metadata parsing/decision logic shared between `build.rs`
+//! (which gathers the raw inputs by shelling out to `git`/`rustc`/
+//! reading files) and this crate, so it can be unit tested without a
+//! real git checkout or process spawning. `build.rs` pulls this module
+//! in with `#[path = "src/build_support.rs"] mod build_support;`.
+
+use std::collections::HashMap;
+
+/// Parse `name = "..."` / `version = "..."` pairs out of `Cargo.lock`
+/// contents without pulling in a TOML parser as a build dependency.
+pub fn parse_lockfile_dependencies(content: &str) -> Vec<(String, String)> {
+    let mut dependencies = Vec::new();
+    let mut current_name: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line == "[[package]]" {
+            current_name = None;
+        } else if let Some(name) = line.strip_prefix("name = ") {
+            current_name = Some(name.trim_matches('"').to_string());
+        } else if let Some(version) = line.strip_prefix("version = ") {
+            if let Some(name) = current_name.take() {
+                dependencies.push((name, version.trim_matches('"').to_string()));
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Parse a flat `key = "value"` `build_meta.toml` fallback file.
+pub fn parse_build_meta(content: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().to_string();
+            let value = value.trim().trim_matches('"').trim_matches('\'').to_string();
+            fields.insert(key, value);
+        }
+    }
+
+    fields
+}
+
+/// Reconstruct enabled feature names from the `CARGO_FEATURE_*` suffixes
+/// Cargo sets in the build environment.
+///
+/// This is inherently lossy: Cargo uppercases a feature name and turns
+/// every `-` into `_` to form the env var, so `foo-bar` and `foo_bar`
+/// both produce `CARGO_FEATURE_FOO_BAR`. Blanket-replacing `_` back to
+/// `-` guesses wrong for any feature whose real name contains a literal
+/// underscore — there is no way to recover the original spelling from
+/// the env var alone.
+pub fn reconstruct_features<'a>(env_keys: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut features: Vec<String> = env_keys
+        .filter_map(|key| key.strip_prefix("CARGO_FEATURE_"))
+        .map(|f| f.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+    features.dedup();
+    features
+}
+
+/// Resolve a single git-derived field, preferring (in order) a live
+/// `git` result, then an env var override from the packaging step, then
+/// the committed `build_meta.toml` fallback, then `"unknown"`.
+pub fn resolve_git_field(
+    env_override: Option<String>,
+    live: Option<String>,
+    build_meta: &HashMap<String, String>,
+    meta_key: &str,
+) -> String {
+    live.or_else(|| env_override.filter(|v| !v.is_empty()))
+        .or_else(|| build_meta.get(meta_key).cloned())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Parse the Rust release channel (`stable`, `beta`, or `nightly`) out of
+/// `rustc -vV`'s `release:` line, e.g. `1.80.0-nightly` -> `nightly`.
+pub fn parse_rust_channel(rustc_vv_output: &str) -> Option<String> {
+    for line in rustc_vv_output.lines() {
+        if let Some(release) = line.strip_prefix("release: ") {
+            return Some(if release.contains("-nightly") {
+                "nightly".to_string()
+            } else if release.contains("-beta") {
+                "beta".to_string()
+            } else {
+                "stable".to_string()
+            });
+        }
+    }
+    None
+}
+
+/// Identify the CI system this build ran on, if any, by checking the
+/// well-known environment variable each one sets. Defaults to `"local"`
+/// for a developer machine. `is_set` abstracts the actual env lookup so
+/// this can run without touching the process environment in tests.
+pub fn detect_ci_system(is_set: impl Fn(&str) -> bool) -> String {
+    if is_set("GITHUB_ACTIONS") {
+        "github-actions".to_string()
+    } else if is_set("GITLAB_CI") {
+        "gitlab-ci".to_string()
+    } else if is_set("BUILDKITE") {
+        "buildkite".to_string()
+    } else if is_set("JENKINS_URL") {
+        "jenkins".to_string()
+    } else {
+        "local".to_string()
+    }
+}
+
+/// Compose the canonical version string, reconciling the pyproject
+/// baseline with git tag/dirty state:
+///
+/// 1. clean tree, `HEAD` exactly tagged `v<version>` -> `<version>`
+/// 2. dirty tree -> `<version>+<commit>-modified`
+/// 3. clean tree, no matching tag -> `<version>+<commit>`
+/// 4. no git commit known -> `<version>`
+pub fn compute_full_version(
+    version: &str,
+    git_commit: &str,
+    is_dirty: bool,
+    exact_tag: Option<&str>,
+) -> String {
+    if git_commit == "unknown" {
+        return version.to_string();
+    }
+
+    if !is_dirty && exact_tag == Some(format!("v{version}").as_str()) {
+        version.to_string()
+    } else if is_dirty {
+        format!("{version}+{git_commit}-modified")
+    } else {
+        format!("{version}+{git_commit}")
+    }
+}
+
+/// Parse `git status --porcelain` output into one trimmed entry per
+/// modified file, dropping blank lines.
+pub fn parse_dirty_files(porcelain_output: &str) -> Vec<String> {
+    porcelain_output
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lockfile_dependencies_parses_packages_in_order() {
+        let lockfile = r#"
+# This file is automatically generated
+version = 3
+
+[[package]]
+name = "axum"
+version = "0.7.5"
+source = "registry+..."
+
+[[package]]
+name = "tokio"
+version = "1.38.0"
+"#;
+        assert_eq!(
+            parse_lockfile_dependencies(lockfile),
+            vec![
+                ("axum".to_string(), "0.7.5".to_string()),
+                ("tokio".to_string(), "1.38.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lockfile_dependencies_empty_on_malformed_input() {
+        assert_eq!(parse_lockfile_dependencies("not a lockfile at all"), vec![]);
+    }
+
+    #[test]
+    fn build_meta_parses_flat_key_value_pairs() {
+        let toml = "\n# comment\ncommit = \"abc1234\"\nbranch = 'release/1.2'\n\n";
+        let fields = parse_build_meta(toml);
+        assert_eq!(fields.get("commit").map(String::as_str), Some("abc1234"));
+        assert_eq!(fields.get("branch").map(String::as_str), Some("release/1.2"));
+    }
+
+    #[test]
+    fn build_meta_ignores_lines_without_equals() {
+        let fields = parse_build_meta("not-a-valid-line\ncommit = \"abc\"");
+        assert_eq!(fields.len(), 1);
+    }
+
+    #[test]
+    fn reconstruct_features_sorts_and_dedups() {
+        let keys = [
+            "CARGO_FEATURE_METRICS",
+            "CARGO_FEATURE_GRPC",
+            "CARGO_FEATURE_METRICS",
+            "OTHER_VAR",
+        ];
+        assert_eq!(
+            reconstruct_features(keys.into_iter()),
+            vec!["grpc".to_string(), "metrics".to_string()]
+        );
+    }
+
+    #[test]
+    fn resolve_git_field_prefers_live_over_env_and_fallback() {
+        let meta = HashMap::from([("commit".to_string(), "fallback".to_string())]);
+        assert_eq!(
+            resolve_git_field(Some("env".to_string()), Some("live".to_string()), &meta, "commit"),
+            "live"
+        );
+    }
+
+    #[test]
+    fn resolve_git_field_uses_env_override_when_git_unavailable() {
+        let meta = HashMap::from([("commit".to_string(), "fallback".to_string())]);
+        assert_eq!(
+            resolve_git_field(Some("env".to_string()), None, &meta, "commit"),
+            "env"
+        );
+    }
+
+    #[test]
+    fn resolve_git_field_uses_build_meta_when_nothing_else_available() {
+        let meta = HashMap::from([("commit".to_string(), "fallback".to_string())]);
+        assert_eq!(resolve_git_field(None, None, &meta, "commit"), "fallback");
+    }
+
+    #[test]
+    fn resolve_git_field_defaults_to_unknown() {
+        assert_eq!(resolve_git_field(None, None, &HashMap::new(), "commit"), "unknown");
+    }
+
+    #[test]
+    fn full_version_clean_and_exactly_tagged() {
+        assert_eq!(
+            compute_full_version("1.2.3", "abc1234", false, Some("v1.2.3")),
+            "1.2.3"
+        );
+    }
+
+    #[test]
+    fn full_version_dirty() {
+        assert_eq!(
+            compute_full_version("1.2.3", "abc1234", true, Some("v1.2.3")),
+            "1.2.3+abc1234-modified"
+        );
+    }
+
+    #[test]
+    fn full_version_clean_but_untagged() {
+        assert_eq!(
+            compute_full_version("1.2.3", "abc1234", false, None),
+            "1.2.3+abc1234"
+        );
+    }
+
+    #[test]
+    fn full_version_no_git() {
+        assert_eq!(
+            compute_full_version("1.2.3", "unknown", false, None),
+            "1.2.3"
+        );
+    }
+
+    #[test]
+    fn rust_channel_detects_nightly_and_beta_and_stable() {
+        assert_eq!(
+            parse_rust_channel("release: 1.80.0-nightly\nhost: x86_64"),
+            Some("nightly".to_string())
+        );
+        assert_eq!(
+            parse_rust_channel("release: 1.80.0-beta.3"),
+            Some("beta".to_string())
+        );
+        assert_eq!(
+            parse_rust_channel("release: 1.80.0"),
+            Some("stable".to_string())
+        );
+        assert_eq!(parse_rust_channel("no release line here"), None);
+    }
+
+    #[test]
+    fn ci_system_detects_each_provider_and_defaults_local() {
+        assert_eq!(detect_ci_system(|k| k == "GITHUB_ACTIONS"), "github-actions");
+        assert_eq!(detect_ci_system(|k| k == "GITLAB_CI"), "gitlab-ci");
+        assert_eq!(detect_ci_system(|k| k == "BUILDKITE"), "buildkite");
+        assert_eq!(detect_ci_system(|k| k == "JENKINS_URL"), "jenkins");
+        assert_eq!(detect_ci_system(|_| false), "local");
+    }
+
+    #[test]
+    fn dirty_files_trims_and_drops_blank_lines() {
+        let porcelain = " M src/main.rs \n\n?? new_file.rs\n";
+        assert_eq!(
+            parse_dirty_files(porcelain),
+            vec!["M src/main.rs".to_string(), "?? new_file.rs".to_string()]
+        );
+    }
+}
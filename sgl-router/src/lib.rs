@@ -0,0 +1,2 @@
+pub mod build_info;
+pub mod build_support;
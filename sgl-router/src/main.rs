@@ -0,0 +1,10 @@
+use sgl_router::build_info::BuildInfo;
+
+fn main() {
+    if std::env::args().any(|arg| arg == "--version" || arg == "-V") {
+        println!("{}", BuildInfo::current());
+        return;
+    }
+
+    // TODO: parse the rest of the CLI args and start the HTTP/gRPC router.
+}